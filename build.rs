@@ -0,0 +1,212 @@
+//! Generates one zero-sized, strongly-typed constructor per macro listed in
+//! `macros.toml`, plus a `KnownMacro` enum over all of them. This catches a
+//! mistyped dimension (which would otherwise only surface as a subckt-name
+//! mismatch during simulation) at build time instead.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+#[derive(serde::Deserialize)]
+struct MacroMetadata {
+    name: String,
+    width: usize,
+    depth: usize,
+    mask_width: usize,
+    mux_ratio: usize,
+    netlist_path: String,
+    #[serde(default)]
+    dialect: Dialect,
+    #[serde(default, rename = "ports")]
+    naming: Option<MacroNaming>,
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Dialect {
+    #[default]
+    Spice,
+    Ngspice,
+    Hspice,
+    Spectre,
+}
+
+#[derive(serde::Deserialize)]
+struct MacroNaming {
+    subckt_name: Option<String>,
+    addr: Option<String>,
+    we: Option<String>,
+    wmask: Option<String>,
+    din: Option<String>,
+    dout: Option<String>,
+    vdd: Option<String>,
+    vss: Option<String>,
+    clk: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct MacroMetadataFile {
+    #[serde(rename = "macro", default)]
+    macros: Vec<MacroMetadata>,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=macros.toml");
+
+    let contents = fs::read_to_string("macros.toml").expect("failed to read macros.toml");
+    let file: MacroMetadataFile = toml::from_str(&contents).expect("failed to parse macros.toml");
+
+    let mut code = String::new();
+    let mut enum_variants = String::new();
+    let mut enum_match = String::new();
+
+    for entry in &file.macros {
+        assert!(
+            entry.depth.is_power_of_two(),
+            "macro `{}` has depth {}, which is not a power of two",
+            entry.name,
+            entry.depth
+        );
+        check_name_matches_dimensions(entry);
+
+        let struct_name = to_pascal_case(&entry.name);
+        let dialect_variant = match entry.dialect {
+            Dialect::Spice => "Spice",
+            Dialect::Ngspice => "Ngspice",
+            Dialect::Hspice => "Hspice",
+            Dialect::Spectre => "Spectre",
+        };
+        let naming = naming_literal(entry.naming.as_ref());
+
+        writeln!(
+            code,
+            "/// Typed constructor for the `{name}` macro.\n\
+             #[derive(Clone, Copy, Debug)]\n\
+             pub struct {struct_name};\n\n\
+             impl {struct_name} {{\n    \
+                 /// Builds the [`SramMacro`] for the `{name}` macro.\n    \
+                 pub fn macro_def() -> SramMacro {{\n        \
+                     SramMacro {{\n            \
+                         width: {width},\n            \
+                         depth: {depth},\n            \
+                         mask_width: {mask_width},\n            \
+                         mux_ratio: {mux_ratio},\n            \
+                         netlist_path: std::path::PathBuf::from(r#\"{netlist_path}\"#),\n            \
+                         dialect: spice::Dialect::{dialect_variant},\n            \
+                         naming: {naming},\n        \
+                     }}\n    \
+                 }}\n\
+             }}\n",
+            name = entry.name,
+            struct_name = struct_name,
+            width = entry.width,
+            depth = entry.depth,
+            mask_width = entry.mask_width,
+            mux_ratio = entry.mux_ratio,
+            netlist_path = entry.netlist_path,
+        )
+        .unwrap();
+
+        writeln!(enum_variants, "    {struct_name},").unwrap();
+        writeln!(
+            enum_match,
+            "            KnownMacro::{struct_name} => {struct_name}::macro_def(),"
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        code,
+        "/// Enumerates every macro described in `macros.toml`.\n\
+         #[derive(Clone, Copy, Debug, PartialEq, Eq)]\n\
+         pub enum KnownMacro {{\n{enum_variants}}}\n\n\
+         impl KnownMacro {{\n    \
+             /// Builds the [`SramMacro`] for this variant.\n    \
+             pub fn macro_def(&self) -> SramMacro {{\n        \
+                 match self {{\n{enum_match}        \
+                 }}\n    \
+             }}\n\
+         }}\n",
+    )
+    .unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("sram_macros.rs"), code)
+        .expect("failed to write generated macro definitions");
+}
+
+/// Emits a `NetlistNaming { .. }` literal for `naming`, overriding only the
+/// fields the metadata set and taking the rest from `NetlistNaming::sram22()`
+/// via functional update syntax, so the generated code never duplicates
+/// those defaults (they stay defined once, in `src/lib.rs`). Mirrors
+/// `CatalogNaming::into_naming` in `src/catalog.rs`, which resolves the same
+/// `[macro.ports]` table shape at load time instead of at build time.
+fn naming_literal(naming: Option<&MacroNaming>) -> String {
+    let Some(naming) = naming else {
+        return "NetlistNaming::sram22()".to_string();
+    };
+
+    let overrides: Vec<(&str, &Option<String>)> = vec![
+        ("subckt_name", &naming.subckt_name),
+        ("addr", &naming.addr),
+        ("we", &naming.we),
+        ("wmask", &naming.wmask),
+        ("din", &naming.din),
+        ("dout", &naming.dout),
+        ("vdd", &naming.vdd),
+        ("vss", &naming.vss),
+        ("clk", &naming.clk),
+    ]
+    .into_iter()
+    .filter_map(|(field, value)| value.as_ref().map(|v| (field, v)))
+    .collect();
+
+    if overrides.is_empty() {
+        return "NetlistNaming::sram22()".to_string();
+    }
+
+    let mut fields = String::new();
+    for (field, value) in overrides {
+        write!(fields, "{field}: {value:?}.to_string(), ").unwrap();
+    }
+    format!("NetlistNaming {{ {fields}..NetlistNaming::sram22() }}")
+}
+
+/// Checks that `entry.name` encodes the same dimensions as its
+/// `width`/`depth`/`mux_ratio`/`mask_width` fields, modulo the `sram`/
+/// `sram22` naming prefix. This is the check that makes a mistyped
+/// dimension a build failure instead of a footgun that only surfaces as a
+/// subckt-name mismatch during simulation.
+fn check_name_matches_dimensions(entry: &MacroMetadata) {
+    let bits = entry.width / entry.mask_width;
+    let expected_suffix = format!(
+        "{}x{}m{}w{}",
+        entry.depth, entry.width, entry.mux_ratio, bits
+    );
+    let actual_suffix = entry
+        .name
+        .strip_prefix("sram22")
+        .unwrap_or(&entry.name)
+        .trim_start_matches(|c: char| !c.is_ascii_digit());
+    assert_eq!(
+        actual_suffix, expected_suffix,
+        "macro `{}` has dimensions width={}, depth={}, mux_ratio={}, mask_width={} \
+         (rendered as `{}`), but its name does not end in that suffix",
+        entry.name, entry.width, entry.depth, entry.mux_ratio, entry.mask_width, expected_suffix
+    );
+}
+
+/// Converts a `snake_case` metadata name into an `UpperCamelCase` identifier.
+fn to_pascal_case(s: &str) -> String {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,203 @@
+//! Loading [`SramMacro`] catalogs from a declarative TOML file.
+//!
+//! A catalog is a list of `[[macro]]` tables, each describing the
+//! dimensions and netlist location of one macro:
+//!
+//! ```toml
+//! [[macro]]
+//! width = 64
+//! depth = 512
+//! mask_width = 8
+//! mux_ratio = 4
+//! netlist_path = "sram22_512x64m4w8/pex/schematic.pex.spice"
+//! dialect = "spice"
+//! ```
+//!
+//! This keeps a foundry-macro directory describable declaratively and in
+//! sync with the filesystem, rather than requiring a Rust literal per macro.
+
+use crate::{NetlistNaming, SramMacro};
+use serde::Deserialize;
+use spice::Dialect;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// An error encountered while loading a macro catalog.
+#[derive(Debug, Error)]
+pub enum CatalogError {
+    /// The catalog file could not be read.
+    #[error("failed to read catalog file {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// The catalog file was not valid TOML, or did not match the expected
+    /// schema.
+    #[error("failed to parse catalog file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    /// A `[[macro]]` entry's `depth` was not a power of two, which
+    /// [`SramMacro::addr_width`] requires since it computes the address
+    /// width with [`usize::ilog2`].
+    #[error("macro catalog entry {index} has depth {depth}, which is not a power of two")]
+    DepthNotPowerOfTwo { index: usize, depth: usize },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CatalogFile {
+    #[serde(rename = "macro", default)]
+    macros: Vec<CatalogEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CatalogEntry {
+    width: usize,
+    depth: usize,
+    mask_width: usize,
+    mux_ratio: usize,
+    netlist_path: PathBuf,
+    #[serde(default)]
+    dialect: Dialect,
+    /// Per-signal pin naming overrides. Any field left unset falls back to
+    /// [`NetlistNaming::sram22`]'s value for that signal.
+    #[serde(default, rename = "ports")]
+    naming: Option<CatalogNaming>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct CatalogNaming {
+    subckt_name: Option<String>,
+    addr: Option<String>,
+    we: Option<String>,
+    wmask: Option<String>,
+    din: Option<String>,
+    dout: Option<String>,
+    vdd: Option<String>,
+    vss: Option<String>,
+    clk: Option<String>,
+}
+
+impl CatalogNaming {
+    fn into_naming(self) -> NetlistNaming {
+        let default = NetlistNaming::sram22();
+        NetlistNaming {
+            subckt_name: self.subckt_name.unwrap_or(default.subckt_name),
+            addr: self.addr.unwrap_or(default.addr),
+            we: self.we.unwrap_or(default.we),
+            wmask: self.wmask.unwrap_or(default.wmask),
+            din: self.din.unwrap_or(default.din),
+            dout: self.dout.unwrap_or(default.dout),
+            vdd: self.vdd.unwrap_or(default.vdd),
+            vss: self.vss.unwrap_or(default.vss),
+            clk: self.clk.unwrap_or(default.clk),
+        }
+    }
+}
+
+/// Loads a list of [`SramMacro`]s from the TOML catalog at `path`.
+///
+/// Returns [`CatalogError::DepthNotPowerOfTwo`] if any entry's `depth` is
+/// not a power of two.
+pub fn load_catalog(path: impl AsRef<Path>) -> Result<Vec<SramMacro>, CatalogError> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path).map_err(|source| CatalogError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let file: CatalogFile = toml::from_str(&contents).map_err(|source| CatalogError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    file.macros
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            if !entry.depth.is_power_of_two() {
+                return Err(CatalogError::DepthNotPowerOfTwo {
+                    index,
+                    depth: entry.depth,
+                });
+            }
+            Ok(SramMacro {
+                width: entry.width,
+                depth: entry.depth,
+                mask_width: entry.mask_width,
+                mux_ratio: entry.mux_ratio,
+                netlist_path: entry.netlist_path,
+                dialect: entry.dialect,
+                naming: entry.naming.unwrap_or_default().into_naming(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_catalog(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, contents).expect("failed to write catalog fixture");
+        path
+    }
+
+    #[test]
+    fn loads_macros_from_catalog() {
+        let path = write_catalog(
+            "sram22test_catalog_ok.toml",
+            r#"
+            [[macro]]
+            width = 64
+            depth = 512
+            mask_width = 8
+            mux_ratio = 4
+            netlist_path = "sram22_512x64m4w8/pex/schematic.pex.spice"
+
+            [[macro]]
+            width = 32
+            depth = 256
+            mask_width = 8
+            mux_ratio = 2
+            netlist_path = "sram22_256x32m2w8/pex/schematic.pex.spice"
+            dialect = "ngspice"
+
+            [macro.ports]
+            addr = "A[{i}]"
+            "#,
+        );
+
+        let macros = load_catalog(&path).expect("failed to load catalog");
+        assert_eq!(macros.len(), 2);
+        assert_eq!(macros[0].width(), 64);
+        assert_eq!(macros[0].addr_width(), 9);
+        assert_eq!(macros[1].mask_width(), 8);
+        assert_eq!(macros[0].naming.addr, NetlistNaming::sram22().addr);
+        assert_eq!(macros[1].naming.addr, "A[{i}]");
+    }
+
+    #[test]
+    fn rejects_depth_that_is_not_a_power_of_two() {
+        let path = write_catalog(
+            "sram22test_catalog_bad_depth.toml",
+            r#"
+            [[macro]]
+            width = 64
+            depth = 500
+            mask_width = 8
+            mux_ratio = 4
+            netlist_path = "sram22_500x64m4w8/pex/schematic.pex.spice"
+            "#,
+        );
+
+        let err = load_catalog(&path).expect_err("depth 500 is not a power of two");
+        assert!(matches!(
+            err,
+            CatalogError::DepthNotPowerOfTwo { depth: 500, .. }
+        ));
+    }
+}
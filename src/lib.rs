@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use sky130pdk::Sky130Pdk;
-use spice::Spice;
+use spice::{Dialect, ScirConverter, Spice};
 use std::path::PathBuf;
 use substrate::arcstr::ArcStr;
 use substrate::block::Block;
@@ -9,24 +9,44 @@ use substrate::io::schematic::HardwareType;
 use substrate::io::{Array, InOut, Input, Io, Output, Signal};
 use substrate::schematic::{CellBuilder, ExportsNestedData, Schematic};
 
+pub mod catalog;
+pub mod tb;
+
+// Typed per-macro constructors and the `KnownMacro` enum, generated by
+// `build.rs` from `macros.toml`.
+include!(concat!(env!("OUT_DIR"), "/sram_macros.rs"));
+
 #[derive(Io, Clone, Debug)]
 pub struct SramIo {
-    addr: Input<Array<Signal>>,
-    din: Input<Array<Signal>>,
-    we: Input<Signal>,
-    wmask: Input<Array<Signal>>,
-    clk: Input<Signal>,
-    dout: Output<Array<Signal>>,
-    vdd: InOut<Signal>,
-    vss: InOut<Signal>,
+    pub(crate) addr: Input<Array<Signal>>,
+    pub(crate) din: Input<Array<Signal>>,
+    pub(crate) we: Input<Signal>,
+    pub(crate) wmask: Input<Array<Signal>>,
+    pub(crate) clk: Input<Signal>,
+    pub(crate) dout: Output<Array<Signal>>,
+    pub(crate) vdd: InOut<Signal>,
+    pub(crate) vss: InOut<Signal>,
 }
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct SramMacro {
-    width: usize,
-    depth: usize,
-    mask_width: usize,
-    mux_ratio: usize,
-    netlist_path: PathBuf,
+    pub(crate) width: usize,
+    pub(crate) depth: usize,
+    pub(crate) mask_width: usize,
+    pub(crate) mux_ratio: usize,
+    pub(crate) netlist_path: PathBuf,
+    /// The SPICE dialect used to parse `netlist_path`.
+    ///
+    /// Defaults to [`Dialect::Spice`] to preserve the behavior of macros
+    /// created before this field existed.
+    #[serde(default)]
+    pub(crate) dialect: Dialect,
+    /// The subckt name template and per-signal pin naming convention used
+    /// by `netlist_path`.
+    ///
+    /// Defaults to [`NetlistNaming::sram22`] to preserve the behavior of
+    /// macros created before this field existed.
+    #[serde(default = "NetlistNaming::sram22")]
+    pub(crate) naming: NetlistNaming,
 }
 
 impl SramMacro {
@@ -34,6 +54,76 @@ impl SramMacro {
     pub fn addr_width(&self) -> usize {
         self.depth.ilog2() as usize
     }
+
+    /// The width of the `din`/`dout` ports, in bits.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The width of the `wmask` port, in bits.
+    pub fn mask_width(&self) -> usize {
+        self.mask_width
+    }
+
+    /// The subckt name for this macro's netlist, rendered from
+    /// [`NetlistNaming::subckt_name`].
+    fn subckt_name(&self) -> String {
+        self.naming
+            .subckt_name
+            .replace("{depth}", &self.depth.to_string())
+            .replace("{width}", &self.width.to_string())
+            .replace("{mux}", &self.mux_ratio.to_string())
+            .replace("{bits}", &(self.width / self.mask_width).to_string())
+    }
+}
+
+/// A subckt name template and per-signal pin naming convention for an
+/// [`SramMacro`]'s netlist.
+///
+/// Signal patterns that address an individual bit (`addr`, `wmask`, `din`,
+/// `dout`) contain an `{i}` placeholder for the bit index. The subckt name
+/// template may use `{depth}`, `{width}`, `{mux}`, and `{bits}` placeholders.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetlistNaming {
+    pub subckt_name: String,
+    pub addr: String,
+    pub we: String,
+    pub wmask: String,
+    pub din: String,
+    pub dout: String,
+    pub vdd: String,
+    pub vss: String,
+    pub clk: String,
+}
+
+impl NetlistNaming {
+    /// The naming convention used by sram22-generated macros, e.g.
+    /// `sram22_512x64m4w8` with pins `ADDR[i]`, `WE`, `WMASK[i]`, `DIN[i]`,
+    /// `DOUT[i]`, `VDD`, `VSS`, and `CLK`.
+    pub fn sram22() -> Self {
+        Self {
+            subckt_name: "sram22_{depth}x{width}m{mux}w{bits}".to_string(),
+            addr: "ADDR[{i}]".to_string(),
+            we: "WE".to_string(),
+            wmask: "WMASK[{i}]".to_string(),
+            din: "DIN[{i}]".to_string(),
+            dout: "DOUT[{i}]".to_string(),
+            vdd: "VDD".to_string(),
+            vss: "VSS".to_string(),
+            clk: "CLK".to_string(),
+        }
+    }
+
+    /// Renders an indexed signal pattern (e.g. `"ADDR[{i}]"`) for bit `i`.
+    fn indexed(pattern: &str, i: usize) -> String {
+        pattern.replace("{i}", &i.to_string())
+    }
+}
+
+impl Default for NetlistNaming {
+    fn default() -> Self {
+        Self::sram22()
+    }
 }
 
 impl Block for SramMacro {
@@ -67,31 +157,24 @@ impl Schematic<Spice> for SramMacro {
         io: &<<Self as Block>::Io as HardwareType>::Bundle,
         cell: &mut CellBuilder<Spice>,
     ) -> substrate::error::Result<Self::NestedData> {
-        let mut scir = Spice::scir_cell_from_file(
-            &self.netlist_path,
-            &format!(
-                "sram22_{}x{}m{}w{}",
-                self.depth,
-                self.width,
-                self.mux_ratio,
-                self.width / self.mask_width
-            ),
-        );
+        let mut scir = ScirConverter::new(self.dialect)
+            .convert_cell_from_file(&self.netlist_path, &self.subckt_name())
+            .map_err(substrate::error::Error::from)?;
 
         for i in 0..self.addr_width() {
-            scir.connect(&format!("ADDR[{i}]"), io.addr[i]);
+            scir.connect(&NetlistNaming::indexed(&self.naming.addr, i), io.addr[i]);
         }
-        scir.connect("WE", io.we);
+        scir.connect(&self.naming.we, io.we);
         for i in 0..self.mask_width {
-            scir.connect(&format!("WMASK[{i}]"), io.wmask[i]);
+            scir.connect(&NetlistNaming::indexed(&self.naming.wmask, i), io.wmask[i]);
         }
         for i in 0..self.width {
-            scir.connect(&format!("DIN[{i}]"), io.din[i]);
-            scir.connect(&format!("DOUT[{i}]"), io.dout[i]);
+            scir.connect(&NetlistNaming::indexed(&self.naming.din, i), io.din[i]);
+            scir.connect(&NetlistNaming::indexed(&self.naming.dout, i), io.dout[i]);
         }
-        scir.connect("VSS", io.vss);
-        scir.connect("VDD", io.vdd);
-        scir.connect("CLK", io.clk);
+        scir.connect(&self.naming.vss, io.vss);
+        scir.connect(&self.naming.vdd, io.vdd);
+        scir.connect(&self.naming.clk, io.clk);
 
         cell.set_scir(scir);
         Ok(())
@@ -132,6 +215,8 @@ mod tests {
             mask_width: 8,
             mux_ratio: 4,
             netlist_path: PathBuf::from("/tools/C/rahulkumar/personal/sram22_sky130_macros/sram22_512x64m4w8/pex/schematic.pex.spice"),
+            dialect: Dialect::Spice,
+            naming: NetlistNaming::sram22(),
         }
     }
 
@@ -145,4 +230,102 @@ mod tests {
             .write_scir_netlist_to_file(&lib.scir, "build/schematic.spice", Default::default())
             .expect("failed to write schematic");
     }
+
+    #[test]
+    fn custom_naming_overrides_sram22_defaults() {
+        let mut sram = sram_512x64m4w8_pex();
+        assert_eq!(sram.subckt_name(), "sram22_512x64m4w8");
+        assert_eq!(NetlistNaming::indexed(&sram.naming.addr, 3), "ADDR[3]");
+
+        sram.naming = NetlistNaming {
+            subckt_name: "macro_{width}x{depth}".to_string(),
+            addr: "A[{i}]".to_string(),
+            we: "CE".to_string(),
+            wmask: "WEN[{i}]".to_string(),
+            din: "D[{i}]".to_string(),
+            dout: "Q[{i}]".to_string(),
+            vdd: "VDDA".to_string(),
+            vss: "VSSA".to_string(),
+            clk: "CK".to_string(),
+        };
+        assert_eq!(sram.subckt_name(), "macro_64x512");
+        assert_eq!(NetlistNaming::indexed(&sram.naming.addr, 3), "A[3]");
+        assert_eq!(sram.naming.we, "CE");
+    }
+
+    #[test]
+    fn generated_macro_matches_hand_written_fixture() {
+        let generated = Sram512x64m4w8::macro_def();
+        assert_eq!(generated, sram_512x64m4w8_pex());
+        assert_eq!(KnownMacro::Sram512x64m4w8.macro_def(), generated);
+    }
+
+    /// The same two-port subckt, written in each of the four dialects this
+    /// crate supports.
+    const GENERIC_SPICE_SUBCKT: &str = "\
+.subckt test_cell A B
+R1 A B 1k
+.ends test_cell
+";
+
+    const NGSPICE_SUBCKT: &str = "\
+* ngspice-flavored netlist with an inline comment
+.subckt test_cell A B $ two-terminal resistor
+R1 A B 1k
+.ends test_cell
+";
+
+    const HSPICE_SUBCKT: &str = "\
+.subckt test_cell A B
++ $ hspice allows continuation lines after the port list
+R1 A B 1k
+.ends test_cell
+";
+
+    const SPECTRE_SUBCKT: &str = "\
+// spectre-flavored netlist: no dot-commands, parenthesized port list
+subckt test_cell (A B)
+    R1 (A B) resistor r=1k
+ends test_cell
+";
+
+    fn write_fixture(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, contents).expect("failed to write netlist fixture");
+        path
+    }
+
+    #[test]
+    fn import_dialects_agree_on_port_connectivity() {
+        let fixtures = [
+            (Dialect::Spice, GENERIC_SPICE_SUBCKT, "test_cell_spice.spice"),
+            (Dialect::Ngspice, NGSPICE_SUBCKT, "test_cell_ngspice.spice"),
+            (Dialect::Hspice, HSPICE_SUBCKT, "test_cell_hspice.spice"),
+            (Dialect::Spectre, SPECTRE_SUBCKT, "test_cell_spectre.scs"),
+        ];
+
+        let mut connectivity = Vec::new();
+        for (dialect, contents, filename) in fixtures {
+            let path = write_fixture(filename, contents);
+            let scir = ScirConverter::new(dialect)
+                .convert_cell_from_file(&path, "test_cell")
+                .expect("failed to parse netlist");
+
+            let mut ports = scir
+                .ports()
+                .map(|p| (p.name().to_string(), p.direction()))
+                .collect::<Vec<_>>();
+            ports.sort_by(|a, b| a.0.cmp(&b.0));
+
+            let mut connections = scir
+                .instances()
+                .flat_map(|inst| inst.connections().map(|c| c.name().to_string()))
+                .collect::<Vec<_>>();
+            connections.sort();
+
+            connectivity.push((ports, connections));
+        }
+
+        assert!(connectivity.windows(2).all(|w| w[0] == w[1]));
+    }
 }
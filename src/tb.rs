@@ -0,0 +1,295 @@
+//! Functional read/write testbench for [`SramMacro`].
+//!
+//! Exercises a macro with a user-supplied sequence of [`SramOperation`]s by
+//! driving `addr`/`din`/`we`/`wmask` with PWL sources synchronized to a
+//! pulsed `clk`, running a transient simulation through Spectre, and
+//! thresholding `dout` against `vdd / 2` at a configurable strobe delay
+//! after each read's clock edge.
+
+use crate::SramMacro;
+use serde::{Deserialize, Serialize};
+use spectre::blocks::{Pulse, Pwl, Vsource};
+use spectre::{Options, Spectre};
+use substrate::arcstr::ArcStr;
+use substrate::block::Block;
+use substrate::io::schematic::HardwareType;
+use substrate::io::{Signal, TestbenchIo};
+use substrate::schematic::{CellBuilder, ExportsNestedData, Instance, Schematic};
+use substrate::simulation::data::{tran, Save};
+use substrate::simulation::{SimController, Testbench};
+
+/// A single operation to apply to the device under test during simulation.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SramOperation {
+    /// Write `data` (masked by `mask`) to `addr`.
+    Write { addr: usize, data: u64, mask: u64 },
+    /// Read `addr` and check that the output matches `expected`.
+    Read { addr: usize, expected: u64 },
+}
+
+/// Timing parameters shared by the clock and stimulus sources.
+///
+/// Stored as integer picoseconds/millivolts (rather than `f64` seconds/volts)
+/// so that [`SramTestbench`] can derive `Hash`/`Eq`, which `substrate::block::Block`
+/// requires.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SramTimingParams {
+    /// The clock period, in picoseconds.
+    pub clk_period_ps: u64,
+    /// The clock rise time, in picoseconds.
+    pub clk_rise_ps: u64,
+    /// The clock fall time, in picoseconds.
+    pub clk_fall_ps: u64,
+    /// The supply voltage, in millivolts. Inputs are driven between 0 and
+    /// this value, and `dout` is thresholded against half of it.
+    pub vdd_mv: u64,
+    /// The delay after a read's clock edge at which `dout` is sampled, in
+    /// picoseconds.
+    pub read_strobe_delay_ps: u64,
+}
+
+impl SramTimingParams {
+    fn clk_period(&self) -> f64 {
+        self.clk_period_ps as f64 * 1e-12
+    }
+
+    fn clk_rise(&self) -> f64 {
+        self.clk_rise_ps as f64 * 1e-12
+    }
+
+    fn clk_fall(&self) -> f64 {
+        self.clk_fall_ps as f64 * 1e-12
+    }
+
+    fn vdd(&self) -> f64 {
+        self.vdd_mv as f64 * 1e-3
+    }
+
+    fn read_strobe_delay(&self) -> f64 {
+        self.read_strobe_delay_ps as f64 * 1e-12
+    }
+}
+
+impl Default for SramTimingParams {
+    fn default() -> Self {
+        Self {
+            clk_period_ps: 5_000,
+            clk_rise_ps: 50,
+            clk_fall_ps: 50,
+            vdd_mv: 1_800,
+            read_strobe_delay_ps: 2_000,
+        }
+    }
+}
+
+/// The outcome of a single [`SramOperation`] applied during simulation.
+#[derive(Clone, Debug)]
+pub struct OperationResult {
+    /// The index of the operation within the testbench's operation sequence.
+    pub index: usize,
+    /// The operation that was applied.
+    pub op: SramOperation,
+    /// The thresholded output bits sampled at the read strobe, if this
+    /// operation was a read.
+    pub actual: Option<u64>,
+}
+
+impl OperationResult {
+    /// Returns whether this operation's expectation, if any, was met.
+    pub fn passed(&self) -> bool {
+        match (&self.op, self.actual) {
+            (SramOperation::Read { expected, .. }, Some(actual)) => *expected == actual,
+            _ => true,
+        }
+    }
+}
+
+/// A transient testbench that exercises an [`SramMacro`] with a sequence of
+/// read and write operations and reports pass/fail per operation.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SramTestbench {
+    dut: SramMacro,
+    ops: Vec<SramOperation>,
+    timing: SramTimingParams,
+}
+
+impl SramTestbench {
+    /// Creates a new testbench that applies `ops` to `dut` using the default
+    /// [`SramTimingParams`].
+    pub fn new(dut: SramMacro, ops: Vec<SramOperation>) -> Self {
+        Self {
+            dut,
+            ops,
+            timing: SramTimingParams::default(),
+        }
+    }
+
+    /// Overrides the default timing parameters.
+    pub fn with_timing(mut self, timing: SramTimingParams) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// The transient simulation's stop time.
+    ///
+    /// Must cover the last operation's capturing clock edge
+    /// (`edge_time(ops.len() - 1)`) plus its read strobe delay, with a full
+    /// extra clock period of margin so a read strobe is never scheduled
+    /// past the end of the simulated window.
+    fn sim_duration(&self) -> f64 {
+        (self.ops.len() as f64 + 1.0) * self.timing.clk_period() + self.timing.read_strobe_delay()
+    }
+
+    /// Clock edge time (the rising edge that samples/applies operation `i`).
+    fn edge_time(&self, i: usize) -> f64 {
+        (i as f64 + 1.0) * self.timing.clk_period()
+    }
+
+    /// Builds a PWL waveform for one input bit, updated just after each
+    /// clock edge so that the new value is settled well before the next one.
+    fn bit_pwl(&self, bit_at: impl Fn(&SramOperation) -> bool) -> Pwl {
+        let settle = self.timing.clk_rise().max(self.timing.clk_fall());
+        let mut points = vec![(0.0, 0.0)];
+        let mut prev = 0.0;
+        for (i, op) in self.ops.iter().enumerate() {
+            let v = if bit_at(op) { self.timing.vdd() } else { 0.0 };
+            let t = if i == 0 {
+                settle
+            } else {
+                // Hold the previous value up to the edge, then ramp.
+                points.push((self.edge_time(i - 1), prev));
+                self.edge_time(i - 1) + settle
+            };
+            points.push((t, v));
+            prev = v;
+        }
+        Pwl { points }
+    }
+}
+
+impl Block for SramTestbench {
+    type Io = TestbenchIo;
+
+    fn id() -> ArcStr {
+        arcstr::literal!("sram_testbench")
+    }
+
+    fn io(&self) -> Self::Io {
+        TestbenchIo::default()
+    }
+}
+
+impl ExportsNestedData for SramTestbench {
+    type NestedData = Instance<SramMacro>;
+}
+
+impl Schematic<Spectre> for SramTestbench {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Spectre>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let vdd = cell.signal("vdd", Signal);
+        let dut = cell.instantiate(self.dut.clone());
+
+        cell.connect(dut.io().vdd, vdd);
+        cell.connect(dut.io().vss, io.vss);
+        cell.instantiate_connected(Vsource::dc(self.timing.vdd()), (vdd, io.vss));
+
+        let clk = cell.signal("clk", Signal);
+        cell.instantiate_connected(
+            Vsource::pulse(Pulse {
+                val0: 0.0,
+                val1: self.timing.vdd(),
+                period: Some(self.timing.clk_period()),
+                width: Some(self.timing.clk_period() / 2.0),
+                rise: Some(self.timing.clk_rise()),
+                fall: Some(self.timing.clk_fall()),
+                delay: Some(self.timing.clk_period()),
+            }),
+            (clk, io.vss),
+        );
+        cell.connect(dut.io().clk, clk);
+
+        for i in 0..self.dut.addr_width() {
+            let net = cell.signal(format!("addr_{i}"), Signal);
+            let pwl = self.bit_pwl(|op| match op {
+                SramOperation::Write { addr, .. } | SramOperation::Read { addr, .. } => {
+                    (addr >> i) & 1 != 0
+                }
+            });
+            cell.instantiate_connected(Vsource::pwl(pwl), (net, io.vss));
+            cell.connect(dut.io().addr[i], net);
+        }
+
+        let we_net = cell.signal("we", Signal);
+        let we_pwl = self.bit_pwl(|op| matches!(op, SramOperation::Write { .. }));
+        cell.instantiate_connected(Vsource::pwl(we_pwl), (we_net, io.vss));
+        cell.connect(dut.io().we, we_net);
+
+        for i in 0..self.dut.mask_width() {
+            let net = cell.signal(format!("wmask_{i}"), Signal);
+            let pwl = self.bit_pwl(|op| match op {
+                SramOperation::Write { mask, .. } => (mask >> i) & 1 != 0,
+                SramOperation::Read { .. } => false,
+            });
+            cell.instantiate_connected(Vsource::pwl(pwl), (net, io.vss));
+            cell.connect(dut.io().wmask[i], net);
+        }
+
+        for i in 0..self.dut.width() {
+            let net = cell.signal(format!("din_{i}"), Signal);
+            let pwl = self.bit_pwl(|op| match op {
+                SramOperation::Write { data, .. } => (data >> i) & 1 != 0,
+                SramOperation::Read { .. } => false,
+            });
+            cell.instantiate_connected(Vsource::pwl(pwl), (net, io.vss));
+            cell.connect(dut.io().din[i], net);
+        }
+
+        Ok(dut)
+    }
+}
+
+impl Testbench<Spectre> for SramTestbench {
+    type Output = Vec<OperationResult>;
+
+    fn run(&self, sim: SimController<Spectre, Self>) -> Self::Output {
+        let dout = Save::save(sim.tb, sim.data().io().dout, sim.ctx);
+
+        let output = sim
+            .simulate(
+                Options::default(),
+                tran::Tran {
+                    stop: self.sim_duration(),
+                    ..Default::default()
+                },
+            )
+            .expect("transient simulation failed");
+
+        let threshold = self.timing.vdd() / 2.0;
+        self.ops
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, op)| {
+                let actual = matches!(op, SramOperation::Read { .. }).then(|| {
+                    let strobe = self.edge_time(i) + self.timing.read_strobe_delay();
+                    (0..self.dut.width()).fold(0u64, |acc, bit| {
+                        let v = output.get_data(&dout[bit]).getv(strobe);
+                        if v > threshold {
+                            acc | (1 << bit)
+                        } else {
+                            acc
+                        }
+                    })
+                });
+                OperationResult {
+                    index: i,
+                    op,
+                    actual,
+                }
+            })
+            .collect()
+    }
+}